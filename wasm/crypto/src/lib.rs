@@ -7,11 +7,15 @@
 // Licensed under MIT License
 
 use wasm_bindgen::prelude::*;
+use std::collections::HashMap;
+use sha1::Sha1;
 use sha2::{Sha256, Sha512, Digest};
-use ed25519_dalek::{Verifier, Signature, VerifyingKey};
-use rsa::{RsaPublicKey, PaddingScheme, PublicKey};
+use ed25519_dalek::{Signer, Verifier, Signature, SigningKey, VerifyingKey};
+use rsa::{RsaPrivateKey, RsaPublicKey, Pkcs1v15Sign};
+use rsa::pkcs1::{DecodeRsaPrivateKey, DecodeRsaPublicKey, EncodeRsaPrivateKey, EncodeRsaPublicKey};
 use rsa::sha2::Sha256 as RsaSha256;
 use base64::{Engine as _, engine::general_purpose};
+use serde::{Serialize, Deserialize};
 
 /// Initialize the WASM module
 /// Call this before using any other functions
@@ -68,6 +72,47 @@ pub fn sha512_hash(data: &[u8]) -> String {
     general_purpose::STANDARD.encode(result)
 }
 
+/// Compute SHA-1 hash of input data
+///
+/// SHA-1 is cryptographically weak; this exists only to support legacy `rsa-sha1`
+/// DKIM signatures for archival and forensic verification. Prefer `sha256_hash`.
+///
+/// # Arguments
+/// * `data` - Input data as bytes
+///
+/// # Returns
+/// Base64-encoded SHA-1 hash
+#[wasm_bindgen]
+pub fn sha1_hash(data: &[u8]) -> String {
+    let mut hasher = Sha1::new();
+    hasher.update(data);
+    let result = hasher.finalize();
+    general_purpose::STANDARD.encode(result)
+}
+
+/// Compute SHA-1 hash (returns raw bytes)
+///
+/// # Arguments
+/// * `data` - Input data as bytes
+///
+/// # Returns
+/// Raw SHA-1 hash bytes
+#[wasm_bindgen]
+pub fn sha1_hash_raw(data: &[u8]) -> Vec<u8> {
+    let mut hasher = Sha1::new();
+    hasher.update(data);
+    hasher.finalize().to_vec()
+}
+
+/// Outcome of a legacy (weak-digest) signature verification
+#[derive(Serialize)]
+pub struct LegacyVerificationResult {
+    pub valid: bool,
+    pub algorithm: String,
+    pub deprecated: bool,
+    pub warning: String,
+}
+
 /// Verify Ed25519 signature (used in DKIM with ed25519-sha256)
 ///
 /// # Arguments
@@ -148,13 +193,66 @@ pub fn verify_rsa_sha256(
     let hash = hasher.finalize();
 
     // Verify signature using PKCS1v15 padding
-    let padding = PaddingScheme::new_pkcs1v15_sign::<RsaSha256>();
-    match public_key.verify(padding, &hash, &sig_bytes) {
+    let scheme = Pkcs1v15Sign::new::<RsaSha256>();
+    match public_key.verify(scheme, &hash, &sig_bytes) {
         Ok(_) => Ok(true),
         Err(_) => Ok(false),
     }
 }
 
+/// Verify RSA signature with SHA-1 (legacy `rsa-sha1`, RFC 6376 original algorithm)
+///
+/// SHA-1 is deprecated for DKIM; this is provided for archival and forensic
+/// verification of old signatures, not for gating trust decisions on new mail.
+///
+/// # Arguments
+/// * `public_key_der` - Base64-encoded DER public key
+/// * `message` - Message that was signed
+/// * `signature` - Base64-encoded RSA signature
+///
+/// # Returns
+/// Verification result carrying a `deprecated`/`warning` flag for UIs to surface
+#[wasm_bindgen]
+pub fn verify_rsa_sha1(
+    public_key_der: &str,
+    message: &[u8],
+    signature: &str,
+) -> Result<JsValue, JsValue> {
+    // Decode base64 inputs
+    let pub_key_bytes = general_purpose::STANDARD
+        .decode(public_key_der)
+        .map_err(|e| JsValue::from_str(&format!("Invalid public key: {}", e)))?;
+
+    let sig_bytes = general_purpose::STANDARD
+        .decode(signature)
+        .map_err(|e| JsValue::from_str(&format!("Invalid signature: {}", e)))?;
+
+    // Parse RSA public key from DER
+    let public_key = RsaPublicKey::from_pkcs1_der(&pub_key_bytes)
+        .map_err(|e| JsValue::from_str(&format!("Invalid RSA key: {}", e)))?;
+
+    // Compute SHA-1 hash of message
+    let mut hasher = Sha1::new();
+    hasher.update(message);
+    let hash = hasher.finalize();
+
+    // Verify signature using PKCS1v15 padding
+    let scheme = Pkcs1v15Sign::new::<Sha1>();
+    let valid = public_key.verify(scheme, &hash, &sig_bytes).is_ok();
+
+    let result = LegacyVerificationResult {
+        valid,
+        algorithm: "rsa-sha1".to_string(),
+        deprecated: true,
+        warning: "rsa-sha1 uses SHA-1, which is cryptographically weak; treat this result \
+                  as informational for archival/forensic use only"
+            .to_string(),
+    };
+
+    serde_wasm_bindgen::to_value(&result)
+        .map_err(|e| JsValue::from_str(&format!("Serialization error: {}", e)))
+}
+
 /// Compute DKIM body hash (SHA-256)
 ///
 /// # Arguments
@@ -177,6 +275,498 @@ pub fn compute_body_hash(body: &[u8], length: usize) -> String {
     general_purpose::STANDARD.encode(result)
 }
 
+/// Verify a DKIM body hash, refusing `l=` body-length signatures by default
+///
+/// # Arguments
+/// * `body` - Canonicalized email body
+/// * `expected_hash` - Base64-encoded body hash from the signature's `bh=` tag
+/// * `length` - Body length limit from the signature's `l=` tag, or `None` if absent
+/// * `allow_partial_length` - Opt into verifying truncated (`l=`-limited) bodies; must be set
+///   explicitly since an `l=` tag lets an attacker append unsigned content after the signed body
+///
+/// # Returns
+/// `true` if the computed hash matches `expected_hash`
+///
+/// # Errors
+/// Returns an error if `length` is `Some` and `allow_partial_length` is `false` (strict mode)
+#[wasm_bindgen]
+pub fn verify_body_hash(
+    body: &[u8],
+    expected_hash: &str,
+    length: Option<usize>,
+    allow_partial_length: bool,
+) -> Result<bool, JsValue> {
+    verify_body_hash_internal(body, expected_hash, length, allow_partial_length)
+        .map_err(|e| JsValue::from_str(&e))
+}
+
+/// Internal DKIM body hash verification
+fn verify_body_hash_internal(
+    body: &[u8],
+    expected_hash: &str,
+    length: Option<usize>,
+    allow_partial_length: bool,
+) -> Result<bool, String> {
+    if length.is_some() && !allow_partial_length {
+        return Err(
+            "Signature carries an l= body-length tag; refusing to verify in strict mode"
+                .to_string(),
+        );
+    }
+
+    // compute_body_hash treats 0 as "no limit", so apply an explicit l= limit here
+    // (l=0 covers an empty body, not the whole body)
+    let data = match length {
+        Some(len) => &body[..len.min(body.len())],
+        None => body,
+    };
+    let computed = compute_body_hash(data, 0);
+    Ok(computed == expected_hash)
+}
+
+/// Parsed DKIM public-key DNS TXT record (the `_domainkey` record, RFC 6376 section 3.6.1)
+#[derive(Serialize)]
+pub struct DkimPublicKey {
+    pub key_type: String,
+    pub key_bytes: Vec<u8>,
+    pub hash_algorithms: Vec<String>,
+    pub service_type: Option<String>,
+    pub flags: Vec<String>,
+    pub revoked: bool,
+}
+
+/// Parse a DKIM public-key DNS TXT record (`v=DKIM1; k=rsa; p=<base64>; h=; s=; t=; n=`)
+///
+/// # Arguments
+/// * `txt_record` - The full contents of the `_domainkey` TXT record
+///
+/// # Returns
+/// A [`DkimPublicKey`] with the decoded key bytes and parsed flags
+#[wasm_bindgen]
+pub fn parse_dkim_public_key(txt_record: &str) -> Result<JsValue, JsValue> {
+    let parsed = parse_dkim_public_key_internal(txt_record)
+        .map_err(|e| JsValue::from_str(&format!("DKIM key record parse error: {}", e)))?;
+
+    serde_wasm_bindgen::to_value(&parsed)
+        .map_err(|e| JsValue::from_str(&format!("Serialization error: {}", e)))
+}
+
+/// Internal DKIM key record parsing
+fn parse_dkim_public_key_internal(txt_record: &str) -> Result<DkimPublicKey, String> {
+    let mut tags: HashMap<String, String> = HashMap::new();
+
+    for entry in txt_record.split(';') {
+        let entry = entry.trim();
+        if entry.is_empty() {
+            continue;
+        }
+
+        let (tag, value) = entry
+            .split_once('=')
+            .ok_or_else(|| format!("Invalid tag (missing '='): {}", entry))?;
+
+        let tag = tag.trim().to_string();
+        let value = value.trim().to_string();
+
+        if tags.insert(tag.clone(), value).is_some() {
+            return Err(format!("Duplicate tag: {}", tag));
+        }
+    }
+
+    if let Some(v) = tags.get("v") {
+        if v != "DKIM1" {
+            return Err(format!("Unsupported key record version: {}", v));
+        }
+    }
+
+    let key_type = tags.get("k").cloned().unwrap_or_else(|| "rsa".to_string());
+
+    let p = tags.get("p").ok_or_else(|| "Missing required p= tag".to_string())?;
+    let p_compact: String = p.chars().filter(|c| !c.is_whitespace()).collect();
+    let revoked = p_compact.is_empty();
+    let key_bytes = if revoked {
+        Vec::new()
+    } else {
+        general_purpose::STANDARD
+            .decode(&p_compact)
+            .map_err(|e| format!("Invalid p= key bytes: {}", e))?
+    };
+
+    let hash_algorithms = tags
+        .get("h")
+        .map(|h| h.split(':').map(|s| s.trim().to_string()).filter(|s| !s.is_empty()).collect())
+        .unwrap_or_default();
+
+    let flags = tags
+        .get("t")
+        .map(|t| t.split(':').map(|s| s.trim().to_string()).filter(|s| !s.is_empty()).collect())
+        .unwrap_or_default();
+
+    Ok(DkimPublicKey {
+        key_type,
+        key_bytes,
+        hash_algorithms,
+        service_type: tags.get("s").cloned(),
+        flags,
+        revoked,
+    })
+}
+
+/// Generated RSA key pair, ready for signing and DNS publication
+#[derive(Serialize)]
+pub struct RsaKeyPair {
+    /// Base64-encoded PKCS#1 DER private key
+    pub private_key: String,
+    /// Base64-encoded PKCS#1 DER public key, ready to publish as the DNS `p=` tag
+    pub public_key_p: String,
+}
+
+/// Generate an RSA key pair for DKIM signing
+///
+/// # Arguments
+/// * `bits` - RSA modulus size in bits (2048 recommended, 1024 minimum per RFC 6376)
+///
+/// # Returns
+/// An [`RsaKeyPair`] with the private key and a ready-to-publish `p=` value
+#[wasm_bindgen]
+pub fn generate_rsa_keypair(bits: usize) -> Result<JsValue, JsValue> {
+    let result = generate_rsa_keypair_internal(bits).map_err(|e| JsValue::from_str(&e))?;
+
+    serde_wasm_bindgen::to_value(&result)
+        .map_err(|e| JsValue::from_str(&format!("Serialization error: {}", e)))
+}
+
+/// Internal RSA key-pair generation
+fn generate_rsa_keypair_internal(bits: usize) -> Result<RsaKeyPair, String> {
+    let mut rng = rand::thread_rng();
+
+    let private_key = RsaPrivateKey::new(&mut rng, bits)
+        .map_err(|e| format!("RSA key generation failed: {}", e))?;
+    let public_key = RsaPublicKey::from(&private_key);
+
+    let private_der = private_key
+        .to_pkcs1_der()
+        .map_err(|e| format!("Failed to encode private key: {}", e))?;
+    let public_der = public_key
+        .to_pkcs1_der()
+        .map_err(|e| format!("Failed to encode public key: {}", e))?;
+
+    Ok(RsaKeyPair {
+        private_key: general_purpose::STANDARD.encode(private_der.as_bytes()),
+        public_key_p: general_purpose::STANDARD.encode(public_der.as_bytes()),
+    })
+}
+
+/// Generated Ed25519 key pair, ready for signing and DNS publication
+#[derive(Serialize)]
+pub struct Ed25519KeyPair {
+    /// Base64-encoded 32-byte private key seed
+    pub private_key: String,
+    /// Base64-encoded 32-byte public key, ready to publish as the DNS `p=` tag
+    pub public_key_p: String,
+}
+
+/// Generate an Ed25519 key pair for DKIM signing (`ed25519-sha256`)
+///
+/// # Returns
+/// An [`Ed25519KeyPair`] with the private key and a ready-to-publish `p=` value
+#[wasm_bindgen]
+pub fn generate_ed25519_keypair() -> Result<JsValue, JsValue> {
+    let result = generate_ed25519_keypair_internal();
+
+    serde_wasm_bindgen::to_value(&result)
+        .map_err(|e| JsValue::from_str(&format!("Serialization error: {}", e)))
+}
+
+/// Internal Ed25519 key-pair generation
+fn generate_ed25519_keypair_internal() -> Ed25519KeyPair {
+    let mut csprng = rand::rngs::OsRng;
+    let signing_key = SigningKey::generate(&mut csprng);
+    let verifying_key = signing_key.verifying_key();
+
+    Ed25519KeyPair {
+        private_key: general_purpose::STANDARD.encode(signing_key.to_bytes()),
+        public_key_p: general_purpose::STANDARD.encode(verifying_key.to_bytes()),
+    }
+}
+
+/// Sign a canonicalized DKIM header block, producing the `b=` signature value
+///
+/// # Arguments
+/// * `private_key` - Base64-encoded private key (PKCS#1 DER for RSA, 32-byte seed for Ed25519)
+/// * `signing_algorithm` - `rsa-sha256`, `rsa-sha1`, or `ed25519-sha256`
+/// * `canonicalized_headers` - The canonicalized signed-header block, including the
+///   `DKIM-Signature` header with `bh=` already filled in and `b=` empty
+/// * `body_hash` - The same body hash that was placed in the `bh=` tag, used to guard
+///   against signing a header block whose body hash doesn't match the caller's intent
+///
+/// # Returns
+/// Base64-encoded signature, ready for the `b=` tag
+#[wasm_bindgen]
+pub fn sign_dkim(
+    private_key: &str,
+    signing_algorithm: &str,
+    canonicalized_headers: &str,
+    body_hash: &str,
+) -> Result<String, JsValue> {
+    sign_dkim_internal(private_key, signing_algorithm, canonicalized_headers, body_hash)
+        .map_err(|e| JsValue::from_str(&e))
+}
+
+/// Check that `canonicalized_headers` carries a `bh=` tag whose value is exactly `body_hash`,
+/// rather than just matching `body_hash` as a substring anywhere in the header block
+fn headers_contain_body_hash_tag(canonicalized_headers: &str, body_hash: &str) -> bool {
+    let needle = format!("bh={}", body_hash);
+
+    canonicalized_headers.match_indices("bh=").any(|(idx, _)| {
+        let after = &canonicalized_headers[idx..];
+        if !after.starts_with(&needle) {
+            return false;
+        }
+
+        matches!(after[needle.len()..].chars().next(), None | Some(';') | Some('\r') | Some('\n'))
+    })
+}
+
+/// Internal DKIM signing
+fn sign_dkim_internal(
+    private_key: &str,
+    signing_algorithm: &str,
+    canonicalized_headers: &str,
+    body_hash: &str,
+) -> Result<String, String> {
+    if !headers_contain_body_hash_tag(canonicalized_headers, body_hash) {
+        return Err(
+            "canonicalized_headers does not contain a bh= tag matching body_hash".to_string(),
+        );
+    }
+
+    let key_bytes = general_purpose::STANDARD
+        .decode(private_key)
+        .map_err(|e| format!("Invalid private key: {}", e))?;
+
+    let message = canonicalized_headers.as_bytes();
+
+    match signing_algorithm {
+        "rsa-sha256" => {
+            let private_key = RsaPrivateKey::from_pkcs1_der(&key_bytes)
+                .map_err(|e| format!("Invalid RSA private key: {}", e))?;
+
+            let mut hasher = RsaSha256::new();
+            hasher.update(message);
+            let hash = hasher.finalize();
+
+            let scheme = Pkcs1v15Sign::new::<RsaSha256>();
+            let signature = private_key
+                .sign(scheme, &hash)
+                .map_err(|e| format!("RSA signing failed: {}", e))?;
+
+            Ok(general_purpose::STANDARD.encode(signature))
+        }
+        "rsa-sha1" => {
+            let private_key = RsaPrivateKey::from_pkcs1_der(&key_bytes)
+                .map_err(|e| format!("Invalid RSA private key: {}", e))?;
+
+            let mut hasher = Sha1::new();
+            hasher.update(message);
+            let hash = hasher.finalize();
+
+            let scheme = Pkcs1v15Sign::new::<Sha1>();
+            let signature = private_key
+                .sign(scheme, &hash)
+                .map_err(|e| format!("RSA signing failed: {}", e))?;
+
+            Ok(general_purpose::STANDARD.encode(signature))
+        }
+        "ed25519-sha256" | "ed25519" => {
+            let key_array: [u8; 32] = key_bytes
+                .try_into()
+                .map_err(|_| "Ed25519 private key must be 32 bytes".to_string())?;
+
+            let signing_key = SigningKey::from_bytes(&key_array);
+            let signature = signing_key.sign(message);
+
+            Ok(general_purpose::STANDARD.encode(signature.to_bytes()))
+        }
+        other => Err(format!("Unsupported signing algorithm: {}", other)),
+    }
+}
+
+/// One ARC instance's verification inputs, already canonicalized by the caller
+///
+/// The caller (JS side, using the parser module's canonicalization exports) is
+/// responsible for assembling `ams_message`/`seal_message` as the canonicalized
+/// header bytes each signature covers, per RFC 8617.
+#[derive(Deserialize)]
+pub struct ArcInstanceInput {
+    pub instance: u32,
+    /// The `cv=` tag from this instance's `ARC-Seal` header
+    pub cv: String,
+    pub ams_algorithm: String,
+    /// Base64 public key: PKCS#1 DER for `rsa-*`, 32 raw bytes for `ed25519-*`
+    pub ams_public_key: String,
+    pub ams_message: String,
+    pub ams_signature: String,
+    pub seal_algorithm: String,
+    pub seal_public_key: String,
+    pub seal_message: String,
+    pub seal_signature: String,
+}
+
+/// Verification outcome for a single ARC instance
+#[derive(Serialize)]
+pub struct ArcInstanceResult {
+    pub instance: u32,
+    pub message_signature_valid: bool,
+    pub seal_valid: bool,
+}
+
+/// Overall ARC chain verification outcome
+#[derive(Serialize)]
+pub struct ArcVerificationResult {
+    /// `none`, `pass`, or `fail`
+    pub chain_status: String,
+    pub instances: Vec<ArcInstanceResult>,
+}
+
+/// Verify an ARC (Authenticated Received Chain) across its header instances
+///
+/// # Arguments
+/// * `instances_json` - JSON array of [`ArcInstanceInput`], one per `i=` instance
+///
+/// # Returns
+/// An [`ArcVerificationResult`] with the per-instance results and overall chain status
+#[wasm_bindgen]
+pub fn verify_arc_chain(instances_json: &str) -> Result<JsValue, JsValue> {
+    let result = verify_arc_chain_internal(instances_json)
+        .map_err(|e| JsValue::from_str(&format!("ARC verification error: {}", e)))?;
+
+    serde_wasm_bindgen::to_value(&result)
+        .map_err(|e| JsValue::from_str(&format!("Serialization error: {}", e)))
+}
+
+fn verify_arc_chain_internal(instances_json: &str) -> Result<ArcVerificationResult, String> {
+    let mut instances: Vec<ArcInstanceInput> = serde_json::from_str(instances_json)
+        .map_err(|e| format!("Invalid ARC instance data: {}", e))?;
+
+    if instances.is_empty() {
+        return Ok(ArcVerificationResult {
+            chain_status: "none".to_string(),
+            instances: Vec::new(),
+        });
+    }
+
+    instances.sort_by_key(|i| i.instance);
+
+    // Instances must form a contiguous 1..N sequence
+    for (idx, inst) in instances.iter().enumerate() {
+        if inst.instance != (idx as u32) + 1 {
+            return Ok(ArcVerificationResult {
+                chain_status: "fail".to_string(),
+                instances: Vec::new(),
+            });
+        }
+    }
+
+    // Instance 1 has no prior chain to validate, so its cv= must be "none";
+    // anything else means the chain was forged or malformed from the start
+    if instances[0].cv != "none" {
+        return Ok(ArcVerificationResult {
+            chain_status: "fail".to_string(),
+            instances: Vec::new(),
+        });
+    }
+
+    // The most recent instance's cv= reflects what that signer saw of the prior chain
+    let most_recent_cv = instances.last().unwrap().cv.clone();
+
+    let mut instance_results = Vec::with_capacity(instances.len());
+    let mut all_valid = true;
+
+    for inst in &instances {
+        let message_signature_valid = verify_arc_signature(
+            &inst.ams_algorithm,
+            &inst.ams_public_key,
+            inst.ams_message.as_bytes(),
+            &inst.ams_signature,
+        )?;
+        let seal_valid = verify_arc_signature(
+            &inst.seal_algorithm,
+            &inst.seal_public_key,
+            inst.seal_message.as_bytes(),
+            &inst.seal_signature,
+        )?;
+
+        all_valid &= message_signature_valid && seal_valid;
+
+        instance_results.push(ArcInstanceResult {
+            instance: inst.instance,
+            message_signature_valid,
+            seal_valid,
+        });
+    }
+
+    let chain_status = if !all_valid || most_recent_cv == "fail" {
+        "fail"
+    } else {
+        "pass"
+    };
+
+    Ok(ArcVerificationResult {
+        chain_status: chain_status.to_string(),
+        instances: instance_results,
+    })
+}
+
+/// Verify a single ARC-Seal or ARC-Message-Signature signature, like a DKIM signature
+fn verify_arc_signature(
+    algorithm: &str,
+    public_key: &str,
+    message: &[u8],
+    signature: &str,
+) -> Result<bool, String> {
+    let sig_bytes = general_purpose::STANDARD
+        .decode(signature)
+        .map_err(|e| format!("Invalid signature: {}", e))?;
+    let key_bytes = general_purpose::STANDARD
+        .decode(public_key)
+        .map_err(|e| format!("Invalid public key: {}", e))?;
+
+    match algorithm {
+        "rsa-sha256" => {
+            let public_key = RsaPublicKey::from_pkcs1_der(&key_bytes)
+                .map_err(|e| format!("Invalid RSA key: {}", e))?;
+            let mut hasher = RsaSha256::new();
+            hasher.update(message);
+            let hash = hasher.finalize();
+            let scheme = Pkcs1v15Sign::new::<RsaSha256>();
+            Ok(public_key.verify(scheme, &hash, &sig_bytes).is_ok())
+        }
+        "rsa-sha1" => {
+            let public_key = RsaPublicKey::from_pkcs1_der(&key_bytes)
+                .map_err(|e| format!("Invalid RSA key: {}", e))?;
+            let mut hasher = Sha1::new();
+            hasher.update(message);
+            let hash = hasher.finalize();
+            let scheme = Pkcs1v15Sign::new::<Sha1>();
+            Ok(public_key.verify(scheme, &hash, &sig_bytes).is_ok())
+        }
+        "ed25519-sha256" | "ed25519" => {
+            let key_array: [u8; 32] = key_bytes
+                .try_into()
+                .map_err(|_| "Ed25519 public key must be 32 bytes".to_string())?;
+            let verifying_key = VerifyingKey::from_bytes(&key_array)
+                .map_err(|e| format!("Invalid Ed25519 key: {}", e))?;
+            let sig_array: [u8; 64] = sig_bytes
+                .try_into()
+                .map_err(|_| "Signature must be 64 bytes".to_string())?;
+            let signature = Signature::from_bytes(&sig_array);
+            Ok(verifying_key.verify(message, &signature).is_ok())
+        }
+        other => Err(format!("Unsupported ARC signing algorithm: {}", other)),
+    }
+}
+
 /// Fast base64 encoding
 #[wasm_bindgen]
 pub fn base64_encode(data: &[u8]) -> String {
@@ -210,4 +800,177 @@ mod tests {
         let decoded = base64_decode(&encoded).unwrap();
         assert_eq!(data.to_vec(), decoded);
     }
+
+    #[test]
+    fn test_verify_body_hash_strict_rejects_l_tag() {
+        let body = b"Hello, World!";
+        let hash = compute_body_hash(body, 0);
+        let result = verify_body_hash_internal(body, &hash, Some(5), false);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_verify_body_hash_without_l_tag() {
+        let body = b"Hello, World!";
+        let hash = compute_body_hash(body, 0);
+        assert!(verify_body_hash_internal(body, &hash, None, false).unwrap());
+    }
+
+    #[test]
+    fn test_sha1() {
+        let data = b"Hello, World!";
+        let hash = sha1_hash(data);
+        assert!(!hash.is_empty());
+    }
+
+    #[test]
+    fn test_sha1_hash_raw_length() {
+        let data = b"Hello, World!";
+        assert_eq!(sha1_hash_raw(data).len(), 20);
+    }
+
+    #[test]
+    fn test_verify_body_hash_allows_partial_length_opt_in() {
+        let body = b"Hello, World! extra unsigned content";
+        let hash = compute_body_hash(body, 13);
+        assert!(verify_body_hash_internal(body, &hash, Some(13), true).unwrap());
+    }
+
+    #[test]
+    fn test_parse_dkim_public_key() {
+        let record = "v=DKIM1; k=rsa; p=SGVsbG8=; h=sha256; s=email; t=y";
+        let key = parse_dkim_public_key_internal(record).unwrap();
+        assert_eq!(key.key_type, "rsa");
+        assert_eq!(key.key_bytes, b"Hello".to_vec());
+        assert_eq!(key.hash_algorithms, vec!["sha256"]);
+        assert_eq!(key.service_type, Some("email".to_string()));
+        assert_eq!(key.flags, vec!["y"]);
+        assert!(!key.revoked);
+    }
+
+    #[test]
+    fn test_parse_dkim_public_key_revoked() {
+        let record = "v=DKIM1; k=rsa; p=";
+        let key = parse_dkim_public_key_internal(record).unwrap();
+        assert!(key.revoked);
+        assert!(key.key_bytes.is_empty());
+    }
+
+    #[test]
+    fn test_parse_dkim_public_key_rejects_missing_p_tag() {
+        let record = "v=DKIM1; k=rsa";
+        assert!(parse_dkim_public_key_internal(record).is_err());
+    }
+
+    #[test]
+    fn test_parse_dkim_public_key_defaults_to_rsa() {
+        let record = "p=SGVsbG8=";
+        let key = parse_dkim_public_key_internal(record).unwrap();
+        assert_eq!(key.key_type, "rsa");
+    }
+
+    #[test]
+    fn test_ed25519_keypair_sign_and_verify_roundtrip() {
+        let keypair = generate_ed25519_keypair_internal();
+
+        let headers = "dkim-signature:v=1; a=ed25519-sha256; bh=AAAA==; b=\r\n";
+        let signature =
+            sign_dkim_internal(&keypair.private_key, "ed25519-sha256", headers, "AAAA==")
+                .unwrap();
+
+        assert!(verify_ed25519(&keypair.public_key_p, headers.as_bytes(), &signature).unwrap());
+    }
+
+    #[test]
+    fn test_rsa_keypair_sign_and_verify_roundtrip_sha256() {
+        let keypair = generate_rsa_keypair_internal(1024).unwrap();
+
+        let headers = "dkim-signature:v=1; a=rsa-sha256; bh=AAAA==; b=\r\n";
+        let signature =
+            sign_dkim_internal(&keypair.private_key, "rsa-sha256", headers, "AAAA==").unwrap();
+
+        assert!(verify_arc_signature(
+            "rsa-sha256",
+            &keypair.public_key_p,
+            headers.as_bytes(),
+            &signature,
+        )
+        .unwrap());
+    }
+
+    #[test]
+    fn test_rsa_keypair_sign_and_verify_roundtrip_sha1() {
+        let keypair = generate_rsa_keypair_internal(1024).unwrap();
+
+        let headers = "dkim-signature:v=1; a=rsa-sha1; bh=AAAA==; b=\r\n";
+        let signature =
+            sign_dkim_internal(&keypair.private_key, "rsa-sha1", headers, "AAAA==").unwrap();
+
+        assert!(verify_arc_signature(
+            "rsa-sha1",
+            &keypair.public_key_p,
+            headers.as_bytes(),
+            &signature,
+        )
+        .unwrap());
+    }
+
+    #[test]
+    fn test_verify_arc_chain_single_valid_instance() {
+        let mut csprng = rand::rngs::OsRng;
+        let signing_key = SigningKey::generate(&mut csprng);
+        let public_key = general_purpose::STANDARD.encode(signing_key.verifying_key().to_bytes());
+
+        let ams_message = b"arc-message-signature covered headers";
+        let ams_signature = general_purpose::STANDARD.encode(signing_key.sign(ams_message).to_bytes());
+        let seal_message = b"arc-seal covered headers";
+        let seal_signature = general_purpose::STANDARD.encode(signing_key.sign(seal_message).to_bytes());
+
+        let instances_json = format!(
+            r#"[{{"instance":1,"cv":"none","ams_algorithm":"ed25519-sha256","ams_public_key":"{pk}","ams_message":"{ams_msg}","ams_signature":"{ams_sig}","seal_algorithm":"ed25519-sha256","seal_public_key":"{pk}","seal_message":"{seal_msg}","seal_signature":"{seal_sig}"}}]"#,
+            pk = public_key,
+            ams_msg = std::str::from_utf8(ams_message).unwrap(),
+            ams_sig = ams_signature,
+            seal_msg = std::str::from_utf8(seal_message).unwrap(),
+            seal_sig = seal_signature,
+        );
+
+        let result = verify_arc_chain_internal(&instances_json).unwrap();
+        assert_eq!(result.chain_status, "pass");
+        assert_eq!(result.instances.len(), 1);
+        assert!(result.instances[0].message_signature_valid);
+        assert!(result.instances[0].seal_valid);
+    }
+
+    #[test]
+    fn test_verify_arc_chain_rejects_non_contiguous_instances() {
+        let instances_json = r#"[{"instance":1,"cv":"none","ams_algorithm":"ed25519-sha256","ams_public_key":"","ams_message":"","ams_signature":"","seal_algorithm":"ed25519-sha256","seal_public_key":"","seal_message":"","seal_signature":""},{"instance":3,"cv":"pass","ams_algorithm":"ed25519-sha256","ams_public_key":"","ams_message":"","ams_signature":"","seal_algorithm":"ed25519-sha256","seal_public_key":"","seal_message":"","seal_signature":""}]"#;
+
+        let result = verify_arc_chain_internal(instances_json).unwrap();
+        assert_eq!(result.chain_status, "fail");
+    }
+
+    #[test]
+    fn test_verify_arc_chain_rejects_non_none_cv_on_first_instance() {
+        let instances_json = r#"[{"instance":1,"cv":"pass","ams_algorithm":"ed25519-sha256","ams_public_key":"","ams_message":"","ams_signature":"","seal_algorithm":"ed25519-sha256","seal_public_key":"","seal_message":"","seal_signature":""}]"#;
+
+        let result = verify_arc_chain_internal(instances_json).unwrap();
+        assert_eq!(result.chain_status, "fail");
+    }
+
+    #[test]
+    fn test_verify_arc_chain_empty_is_none() {
+        let result = verify_arc_chain_internal("[]").unwrap();
+        assert_eq!(result.chain_status, "none");
+    }
+
+    #[test]
+    fn test_sign_dkim_rejects_mismatched_body_hash() {
+        let keypair = generate_ed25519_keypair_internal();
+
+        let headers = "dkim-signature:v=1; a=ed25519-sha256; bh=AAAA==; b=\r\n";
+        let result =
+            sign_dkim_internal(&keypair.private_key, "ed25519-sha256", headers, "different");
+        assert!(result.is_err());
+    }
 }
@@ -18,6 +18,26 @@ pub struct ParsedEmail {
     pub body: String,
 }
 
+/// Parsed `DKIM-Signature:` header tags (RFC 6376 section 3.5)
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct DkimSignature {
+    pub version: String,
+    pub signing_algorithm: String,
+    pub hash_algorithm: String,
+    pub signature: String,
+    pub body_hash: String,
+    pub header_canonicalization: String,
+    pub body_canonicalization: String,
+    pub domain: String,
+    pub selector: String,
+    pub signed_headers: Vec<String>,
+    pub body_length: Option<u64>,
+    pub timestamp: Option<u64>,
+    pub expiration: Option<u64>,
+    pub auid: Option<String>,
+    pub query_methods: Option<String>,
+}
+
 /// Initialize the WASM module
 #[wasm_bindgen(start)]
 pub fn init() {
@@ -188,6 +208,319 @@ pub fn canonicalize_body_simple(body: &str) -> String {
     canonical
 }
 
+/// Canonicalize email body for DKIM (relaxed canonicalization)
+///
+/// # Arguments
+/// * `body` - Raw email body
+///
+/// # Returns
+/// Canonicalized body per RFC 6376 section 3.4.4
+#[wasm_bindgen]
+pub fn canonicalize_body_relaxed(body: &str) -> String {
+    // A genuinely empty body canonicalizes to the empty string (unlike simple
+    // canonicalization, which maps it to a single CRLF)
+    if body.is_empty() {
+        return String::new();
+    }
+
+    // Normalize to CRLF
+    let normalized = body
+        .replace("\r\n", "\n")
+        .replace('\r', "\n")
+        .replace('\n', "\r\n");
+
+    let wsp_run_regex = Regex::new(r"[ \t]+").unwrap();
+
+    // Reduce WSP runs to a single space and strip trailing WSP on each line
+    let mut lines: Vec<String> = normalized
+        .split("\r\n")
+        .map(|line| wsp_run_regex.replace_all(line, " ").trim_end().to_string())
+        .collect();
+
+    // split("\r\n") on a string ending in CRLF yields a trailing empty element; drop it
+    if lines.last().map(|l| l.is_empty()).unwrap_or(false) {
+        lines.pop();
+    }
+
+    // Remove trailing empty lines
+    while lines.last().map(|l| l.is_empty()).unwrap_or(false) {
+        lines.pop();
+    }
+
+    if lines.is_empty() {
+        return "\r\n".to_string();
+    }
+
+    let mut canonical = lines.join("\r\n");
+    canonical.push_str("\r\n");
+    canonical
+}
+
+/// Canonicalize a single email header for DKIM (relaxed canonicalization)
+///
+/// # Arguments
+/// * `header_value` - Full header line, e.g. "Subject:  Hello\r\n world"
+///
+/// # Returns
+/// Canonicalized header as `name:value\r\n` per RFC 6376 section 3.4.2
+#[wasm_bindgen]
+pub fn canonicalize_header_relaxed(header_value: &str) -> Result<String, JsValue> {
+    let colon_pos = header_value
+        .find(':')
+        .ok_or_else(|| JsValue::from_str("Invalid header line: missing colon"))?;
+
+    let name = header_value[..colon_pos].trim().to_lowercase();
+    let raw_value = &header_value[colon_pos + 1..];
+
+    // Unfold continuation lines (CRLF followed by WSP becomes a single space)
+    let unfold_regex = Regex::new(r"\r\n[ \t]+").unwrap();
+    let unfolded = unfold_regex.replace_all(raw_value, " ");
+
+    // Collapse internal WSP runs to a single space, then trim
+    let wsp_run_regex = Regex::new(r"[ \t]+").unwrap();
+    let value = wsp_run_regex.replace_all(&unfolded, " ");
+    let value = value.trim();
+
+    Ok(format!("{}:{}\r\n", name, value))
+}
+
+/// Parse the `DKIM-Signature:` header into a structured [`DkimSignature`]
+///
+/// # Arguments
+/// * `header_value` - Full header line (with or without the `DKIM-Signature:` prefix)
+///
+/// # Returns
+/// JSON-serializable `DkimSignature` with all tags
+#[wasm_bindgen]
+pub fn parse_dkim_signature(header_value: &str) -> Result<JsValue, JsValue> {
+    let parsed = parse_dkim_signature_internal(header_value)
+        .map_err(|e| JsValue::from_str(&format!("DKIM-Signature parse error: {}", e)))?;
+
+    serde_wasm_bindgen::to_value(&parsed)
+        .map_err(|e| JsValue::from_str(&format!("Serialization error: {}", e)))
+}
+
+/// Internal DKIM-Signature parsing
+fn parse_dkim_signature_internal(header_value: &str) -> Result<DkimSignature, String> {
+    let tags = parse_tag_list(header_value)?;
+
+    let get = |tag: &str| tags.get(tag).map(|s| s.as_str());
+    let require = |tag: &str| get(tag).ok_or_else(|| format!("Missing required tag: {}", tag));
+
+    let version = require("v")?.to_string();
+
+    let a = require("a")?;
+    let (signing_algorithm, hash_algorithm) = a
+        .split_once('-')
+        .ok_or_else(|| format!("Invalid a= tag: {}", a))?;
+
+    let c = get("c").unwrap_or("simple/simple");
+    let (header_canonicalization, body_canonicalization) = match c.split_once('/') {
+        Some((h, b)) => (h.to_string(), b.to_string()),
+        None => (c.to_string(), "simple".to_string()),
+    };
+
+    let signed_headers: Vec<String> = require("h")?
+        .split(':')
+        .map(|s| s.trim().to_lowercase())
+        .collect();
+
+    let body_length = match get("l") {
+        Some(l) => Some(l.parse::<u64>().map_err(|_| format!("Invalid l= tag: {}", l))?),
+        None => None,
+    };
+
+    let timestamp = match get("t") {
+        Some(t) => Some(t.parse::<u64>().map_err(|_| format!("Invalid t= tag: {}", t))?),
+        None => None,
+    };
+
+    let expiration = match get("x") {
+        Some(x) => Some(x.parse::<u64>().map_err(|_| format!("Invalid x= tag: {}", x))?),
+        None => None,
+    };
+
+    Ok(DkimSignature {
+        version,
+        signing_algorithm: signing_algorithm.to_string(),
+        hash_algorithm: hash_algorithm.to_string(),
+        signature: strip_fws(require("b")?),
+        body_hash: strip_fws(require("bh")?),
+        header_canonicalization,
+        body_canonicalization,
+        domain: require("d")?.to_string(),
+        selector: require("s")?.to_string(),
+        signed_headers,
+        body_length,
+        timestamp,
+        expiration,
+        auid: get("i").map(|s| s.to_string()),
+        query_methods: get("q").map(|s| s.to_string()),
+    })
+}
+
+/// Parse a `tag=value; tag=value` list (RFC 6376 section 3.2), rejecting duplicate tags
+/// and unfolding any CRLF-WSP folding within the header value.
+fn parse_tag_list(header_value: &str) -> Result<HashMap<String, String>, String> {
+    // Allow callers to pass either the bare tag-list or the full "DKIM-Signature: ..." line
+    let value = match header_value.find(':') {
+        Some(pos) if header_value[..pos].trim().eq_ignore_ascii_case("dkim-signature") => {
+            &header_value[pos + 1..]
+        }
+        _ => header_value,
+    };
+
+    // Unfold continuation lines: CRLF followed by WSP becomes a single space
+    let unfold_regex = Regex::new(r"\r\n[ \t]+").unwrap();
+    let unfolded = unfold_regex.replace_all(value, " ");
+    let unfolded = unfolded.replace(['\r', '\n'], "");
+
+    let mut tags: HashMap<String, String> = HashMap::new();
+    for entry in unfolded.split(';') {
+        let entry = entry.trim();
+        if entry.is_empty() {
+            continue;
+        }
+
+        let (tag, tag_value) = entry
+            .split_once('=')
+            .ok_or_else(|| format!("Invalid tag (missing '='): {}", entry))?;
+
+        let tag = tag.trim().to_string();
+        let tag_value = tag_value.trim().to_string();
+
+        if tags.insert(tag.clone(), tag_value).is_some() {
+            return Err(format!("Duplicate tag: {}", tag));
+        }
+    }
+
+    Ok(tags)
+}
+
+/// Strip folding whitespace from base64 tag values (`b=`, `bh=`)
+fn strip_fws(value: &str) -> String {
+    value.chars().filter(|c| !c.is_whitespace()).collect()
+}
+
+/// One DKIM signature's outcome, for inclusion in an `Authentication-Results` header
+#[derive(Serialize, Deserialize)]
+pub struct DkimResult {
+    /// `pass`, `fail`, `neutral`, `none`, `policy`, `temperror`, or `permerror`
+    pub result: String,
+    pub domain: Option<String>,
+    pub selector: Option<String>,
+    pub auid: Option<String>,
+    pub reason: Option<String>,
+}
+
+/// An ARC chain's outcome, for inclusion in an `Authentication-Results` header
+#[derive(Serialize, Deserialize)]
+pub struct ArcResult {
+    /// `none`, `pass`, or `fail`
+    pub result: String,
+    pub reason: Option<String>,
+}
+
+/// An SPF check's outcome, for inclusion in an `Authentication-Results` header
+#[derive(Serialize, Deserialize)]
+pub struct SpfResult {
+    pub result: String,
+    pub domain: Option<String>,
+    pub reason: Option<String>,
+}
+
+/// A DMARC check's outcome, for inclusion in an `Authentication-Results` header
+#[derive(Serialize, Deserialize)]
+pub struct DmarcResult {
+    pub result: String,
+    pub domain: Option<String>,
+    pub reason: Option<String>,
+}
+
+/// The full set of verification outcomes to summarize in one `Authentication-Results` header
+#[derive(Serialize, Deserialize, Default)]
+pub struct AuthenticationResultsInput {
+    #[serde(default)]
+    pub dkim: Vec<DkimResult>,
+    pub arc: Option<ArcResult>,
+    pub spf: Option<SpfResult>,
+    pub dmarc: Option<DmarcResult>,
+}
+
+/// Build a folded `Authentication-Results:` header from verification outcomes (RFC 7601)
+///
+/// # Arguments
+/// * `authserv_id` - Identifier of the authenticating service (the header's first token)
+/// * `results_json` - JSON-encoded [`AuthenticationResultsInput`]
+///
+/// # Returns
+/// A folded `Authentication-Results:` header string, one result clause per line
+#[wasm_bindgen]
+pub fn build_authentication_results(authserv_id: &str, results_json: &str) -> Result<String, JsValue> {
+    let input: AuthenticationResultsInput = serde_json::from_str(results_json)
+        .map_err(|e| JsValue::from_str(&format!("Invalid results data: {}", e)))?;
+
+    let mut clauses: Vec<String> = Vec::new();
+
+    for dkim in &input.dkim {
+        let mut clause = format!("dkim={}", dkim.result);
+        if let Some(reason) = &dkim.reason {
+            clause.push_str(&format!(" ({})", reason));
+        }
+        if let Some(d) = &dkim.domain {
+            clause.push_str(&format!(" header.d={}", d));
+        }
+        if let Some(s) = &dkim.selector {
+            clause.push_str(&format!(" header.s={}", s));
+        }
+        if let Some(i) = &dkim.auid {
+            clause.push_str(&format!(" header.i={}", i));
+        }
+        clauses.push(clause);
+    }
+
+    if let Some(arc) = &input.arc {
+        let mut clause = format!("arc={}", arc.result);
+        if let Some(reason) = &arc.reason {
+            clause.push_str(&format!(" ({})", reason));
+        }
+        clauses.push(clause);
+    }
+
+    if let Some(spf) = &input.spf {
+        let mut clause = format!("spf={}", spf.result);
+        if let Some(reason) = &spf.reason {
+            clause.push_str(&format!(" ({})", reason));
+        }
+        if let Some(d) = &spf.domain {
+            clause.push_str(&format!(" smtp.mailfrom={}", d));
+        }
+        clauses.push(clause);
+    }
+
+    if let Some(dmarc) = &input.dmarc {
+        let mut clause = format!("dmarc={}", dmarc.result);
+        if let Some(reason) = &dmarc.reason {
+            clause.push_str(&format!(" ({})", reason));
+        }
+        if let Some(d) = &dmarc.domain {
+            clause.push_str(&format!(" header.from={}", d));
+        }
+        clauses.push(clause);
+    }
+
+    if clauses.is_empty() {
+        // No mechanisms ran at all; RFC 7601 calls for "none" rather than an empty result list
+        clauses.push("none".to_string());
+    }
+
+    Ok(format!(
+        "Authentication-Results: {};\r\n {}\r\n",
+        authserv_id,
+        clauses.join(";\r\n ")
+    ))
+}
+
 /// Count Received headers (email hops)
 ///
 /// # Arguments
@@ -231,4 +564,79 @@ mod tests {
         assert!(tokens.contains(&"world".to_string()));
         assert!(tokens.contains(&"test".to_string()));
     }
+
+    #[test]
+    fn test_canonicalize_body_relaxed() {
+        let body = "  Hello  \t World  \r\n\r\nSecond line\r\n\r\n\r\n";
+        let canonical = canonicalize_body_relaxed(body);
+        assert_eq!(canonical, " Hello World\r\n\r\nSecond line\r\n");
+    }
+
+    #[test]
+    fn test_canonicalize_body_relaxed_empty() {
+        assert_eq!(canonicalize_body_relaxed(""), "");
+        assert_eq!(canonicalize_body_relaxed("\r\n\r\n"), "\r\n");
+    }
+
+    #[test]
+    fn test_canonicalize_header_relaxed() {
+        let header = "Subject:  Hello\r\n  World  ";
+        let canonical = canonicalize_header_relaxed(header).unwrap();
+        assert_eq!(canonical, "subject:Hello World\r\n");
+    }
+
+    #[test]
+    fn test_parse_dkim_signature() {
+        let header = "v=1; a=rsa-sha256; c=relaxed/relaxed; d=example.com; s=selector1; \
+            h=from:to:subject; bh=BODYHASH==; b=SIGNATURE==; t=1000000000";
+        let sig = parse_dkim_signature_internal(header).unwrap();
+        assert_eq!(sig.version, "1");
+        assert_eq!(sig.signing_algorithm, "rsa");
+        assert_eq!(sig.hash_algorithm, "sha256");
+        assert_eq!(sig.header_canonicalization, "relaxed");
+        assert_eq!(sig.body_canonicalization, "relaxed");
+        assert_eq!(sig.domain, "example.com");
+        assert_eq!(sig.selector, "selector1");
+        assert_eq!(sig.signed_headers, vec!["from", "to", "subject"]);
+        assert_eq!(sig.body_hash, "BODYHASH==");
+        assert_eq!(sig.signature, "SIGNATURE==");
+        assert_eq!(sig.timestamp, Some(1000000000));
+        assert_eq!(sig.body_length, None);
+    }
+
+    #[test]
+    fn test_parse_dkim_signature_folded_base64() {
+        let header = "v=1; a=rsa-sha256; d=example.com; s=sel; h=from; bh=AAAA==; \
+            b=AAAA\r\n BBBB==";
+        let sig = parse_dkim_signature_internal(header).unwrap();
+        assert_eq!(sig.signature, "AAAABBBB==");
+    }
+
+    #[test]
+    fn test_parse_dkim_signature_rejects_duplicate_tags() {
+        let header = "v=1; v=1; a=rsa-sha256; d=example.com; s=sel; h=from; bh=AAAA==; b=AAAA==";
+        assert!(parse_dkim_signature_internal(header).is_err());
+    }
+
+    #[test]
+    fn test_parse_dkim_signature_rejects_missing_required_tag() {
+        let header = "v=1; a=rsa-sha256; d=example.com; s=sel; h=from; bh=AAAA==";
+        assert!(parse_dkim_signature_internal(header).is_err());
+    }
+
+    #[test]
+    fn test_build_authentication_results() {
+        let results_json = r#"{"dkim":[{"result":"pass","domain":"example.com","selector":"sel1","auid":null,"reason":null}]}"#;
+        let header = build_authentication_results("mx.example.org", results_json).unwrap();
+        assert!(header.starts_with("Authentication-Results: mx.example.org;\r\n"));
+        assert!(header.contains("dkim=pass"));
+        assert!(header.contains("header.d=example.com"));
+        assert!(header.contains("header.s=sel1"));
+    }
+
+    #[test]
+    fn test_build_authentication_results_none_when_empty() {
+        let header = build_authentication_results("mx.example.org", "{}").unwrap();
+        assert!(header.contains("none"));
+    }
 }